@@ -0,0 +1,179 @@
+use egui::{ClippedPrimitive, Context, TexturesDelta};
+use egui_wgpu::renderer::{Renderer, ScreenDescriptor};
+use egui_winit::EventResponse;
+use pixels::{wgpu, PixelsContext};
+use winit::event::WindowEvent;
+use winit::event_loop::EventLoopWindowTarget;
+use winit::window::Window;
+
+use evo_grid::world::World;
+
+/// What the user asked the overlay to do this frame, reported back to `run`
+/// so the simulation's actual pause/step/reset state stays in `AppState`.
+#[derive(Default)]
+pub(crate) struct GuiActions {
+    pub(crate) toggle_paused: bool,
+    pub(crate) step: bool,
+    pub(crate) reset: bool,
+}
+
+/// Manages the egui control/inspection panel drawn over the pixel buffer.
+pub(crate) struct Gui {
+    ctx: Context,
+    state: egui_winit::State,
+    screen_descriptor: ScreenDescriptor,
+    renderer: Renderer,
+    paint_jobs: Vec<ClippedPrimitive>,
+    textures: TexturesDelta,
+}
+
+impl Gui {
+    pub(crate) fn new<T>(
+        event_loop: &EventLoopWindowTarget<T>,
+        width: u32,
+        height: u32,
+        scale_factor: f32,
+        pixels: &pixels::Pixels,
+    ) -> Self {
+        let max_texture_size = pixels.device().limits().max_texture_dimension_2d as usize;
+
+        let ctx = Context::default();
+        let viewport_id = ctx.viewport_id();
+        let state = egui_winit::State::new(
+            ctx.clone(),
+            viewport_id,
+            event_loop,
+            Some(scale_factor),
+            Some(max_texture_size),
+        );
+        let screen_descriptor = ScreenDescriptor {
+            size_in_pixels: [width, height],
+            pixels_per_point: scale_factor,
+        };
+        let renderer = Renderer::new(pixels.device(), pixels.render_texture_format(), None, 1);
+
+        Self {
+            ctx,
+            state,
+            screen_descriptor,
+            renderer,
+            paint_jobs: Vec::new(),
+            textures: TexturesDelta::default(),
+        }
+    }
+
+    /// Lets egui see a winit event before `WinitInputHelper` does, so clicks
+    /// and keystrokes aimed at the panel don't also drive the simulation.
+    pub(crate) fn handle_event(&mut self, window: &Window, event: &WindowEvent) -> EventResponse {
+        self.state.on_window_event(window, event)
+    }
+
+    /// Whether the panel currently wants the pointer, e.g. because the
+    /// cursor is merely hovering a button (not just clicking one). Unlike a
+    /// single event's `EventResponse::consumed`, this reflects egui's hover
+    /// state as of the last `prepare` call, so it stays true for as long as
+    /// the cursor sits over the panel.
+    pub(crate) fn wants_pointer_input(&self) -> bool {
+        self.ctx.wants_pointer_input()
+    }
+
+    /// Whether the panel currently wants the keyboard, e.g. because a text
+    /// field is focused. Unlike `wants_pointer_input`, egui only sets this
+    /// for an actual focused widget, not merely hovering the panel.
+    pub(crate) fn wants_keyboard_input(&self) -> bool {
+        self.ctx.wants_keyboard_input()
+    }
+
+    pub(crate) fn resize(&mut self, width: u32, height: u32) {
+        if width > 0 && height > 0 {
+            self.screen_descriptor.size_in_pixels = [width, height];
+        }
+    }
+
+    pub(crate) fn scale_factor(&mut self, scale_factor: f64) {
+        self.screen_descriptor.pixels_per_point = scale_factor as f32;
+    }
+
+    /// Builds the panel and lays out its widgets. Returns what the user
+    /// asked for so the caller can apply it to the simulation state.
+    pub(crate) fn prepare(&mut self, window: &Window, world: &World, tick: u64, paused: bool) -> GuiActions {
+        let mut actions = GuiActions::default();
+        let raw_input = self.state.take_egui_input(window);
+        let output = self.ctx.run(raw_input, |ctx| {
+            egui::SidePanel::right("evo_controls").show(ctx, |ui| {
+                ui.heading("Evo");
+                ui.label(format!("Tick: {tick}"));
+                ui.label(format!("Creatures: {}", num_creatures(world)));
+                ui.label(format!("Substance: {:.1}", total_substance(world)));
+                ui.separator();
+                if ui.button(if paused { "Resume" } else { "Pause" }).clicked() {
+                    actions.toggle_paused = true;
+                }
+                if ui.add_enabled(paused, egui::Button::new("Step")).clicked() {
+                    actions.step = true;
+                }
+                if ui.button("Reset world").clicked() {
+                    actions.reset = true;
+                }
+            });
+        });
+
+        self.textures.append(output.textures_delta);
+        self.state.handle_platform_output(window, output.platform_output);
+        self.paint_jobs = self.ctx.tessellate(output.shapes, output.pixels_per_point);
+        actions
+    }
+
+    pub(crate) fn render(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        render_target: &wgpu::TextureView,
+        context: &PixelsContext,
+    ) {
+        for (id, image_delta) in &self.textures.set {
+            self.renderer
+                .update_texture(&context.device, &context.queue, *id, image_delta);
+        }
+        self.renderer.update_buffers(
+            &context.device,
+            &context.queue,
+            encoder,
+            &self.paint_jobs,
+            &self.screen_descriptor,
+        );
+
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("egui"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: render_target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        self.renderer.render(&mut rpass, &self.paint_jobs, &self.screen_descriptor);
+        drop(rpass);
+
+        for id in &self.textures.free {
+            self.renderer.free_texture(id);
+        }
+        self.textures.clear();
+    }
+}
+
+fn num_creatures(world: &World) -> usize {
+    world.cells_iter().filter(|cell| cell.creature.is_some()).count()
+}
+
+fn total_substance(world: &World) -> f32 {
+    world
+        .cells_iter()
+        .filter_map(|cell| cell.substance)
+        .map(|substance| substance.amount)
+        .sum()
+}
@@ -6,40 +6,283 @@ use evo_grid::world::{Creature, GridCell, Substance, World};
 use log::{/* debug, */ error};
 use pixels::{Error, Pixels, PixelsBuilder, SurfaceTexture};
 use pixels::wgpu::Color;
+// `std::time::Instant::now()` panics on wasm32-unknown-unknown (no clock
+// syscall); `web_time::Instant` is API-compatible and backs it with
+// `Performance.now()` on that target instead.
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
 use winit::{
     dpi::LogicalSize,
     event::{Event, WindowEvent},
-    event_loop::EventLoop,
+    event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget},
     keyboard::KeyCode,
     window::WindowBuilder,
 };
 use winit::window::Window;
 use winit_input_helper::WinitInputHelper;
 
+mod gui;
+use gui::Gui;
+
+mod post_process;
+use post_process::{PostProcessMode, PostProcessor};
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+#[cfg(target_arch = "wasm32")]
+use winit::platform::web::WindowExtWebSys;
+
+#[cfg(target_os = "android")]
+use winit::platform::android::activity::AndroidApp;
+
 const WIDTH: u32 = 400;
 const HEIGHT: u32 = 300;
 
-fn main() -> Result<(), Error> {
+/// Color painted for a new creature with left-click.
+const PAINT_CREATURE_COLOR: [u8; 3] = [0x22, 0xdd, 0x44];
+/// Substance added (or, while erasing, removed) per painted frame with right-click.
+const PAINT_SUBSTANCE_AMOUNT: f32 = 0.25;
+
+/// Simulation rate, decoupled from the display's frame rate by the
+/// fixed-timestep accumulator in `run`.
+const TICKS_PER_SECOND: f32 = 30.0;
+const TICK_DURATION: f32 = 1.0 / TICKS_PER_SECOND;
+/// Speeds cycled with `]`/`[`; index 2 (1x) is the default.
+const SPEEDS: [f32; 5] = [0.0625, 0.25, 1.0, 4.0, 16.0];
+const DEFAULT_SPEED_INDEX: usize = 2;
+/// Upper bound on how much wall-clock time a single frame can feed into the
+/// accumulator. Without this, a long gap between `last_update` and the first
+/// post-init frame (or any other stall, e.g. a backgrounded Android
+/// activity) would otherwise be caught up all at once -- a burst of ticks
+/// worth the whole gap, up to 16x that at the fastest speed.
+const MAX_FRAME_TIME: f32 = 0.25;
+
+/// State that survives an Android activity's suspend/resume cycle, as
+/// opposed to `Window`/`Pixels`, whose GPU surface is only valid while the
+/// app is in the foreground.
+struct AppState {
+    world: World,
+    input: WinitInputHelper,
+    paused: bool,
+    tick: u64,
+    post_process_mode: PostProcessMode,
+    blend_mode: BlendMode,
+    /// Wall-clock seconds of simulation time not yet converted into ticks.
+    accumulator: f32,
+    speed_index: usize,
+    last_update: Instant,
+    /// Set by Space (or the egui Step button) to run exactly one tick this
+    /// frame regardless of the accumulator, then cleared.
+    step_requested: bool,
+    /// Whether egui wants the pointer this batch (hovering or clicking a
+    /// widget), gating `paint_at_cursor`. Accumulated across every
+    /// `WindowEvent` since the last time `WinitInputHelper` fired and reset
+    /// once that firing's painting has been gated on it. A single event's
+    /// `EventResponse::consumed` isn't enough: `input.update` only returns
+    /// `true` on the batch-ending step event, by which point a fresh
+    /// per-event flag has already gone stale.
+    gui_wants_pointer: bool,
+    /// Same idea as `gui_wants_pointer`, but for the keyboard shortcuts
+    /// (P/Space/M/B/`[`/`]`) and sourced from egui's keyboard focus rather
+    /// than pointer hover — merely resting the cursor over the panel must
+    /// not disable the shortcuts.
+    gui_wants_keyboard: bool,
+}
+
+impl AppState {
+    fn new() -> Self {
+        Self {
+            world: World::new(WIDTH as usize, HEIGHT as usize, evo_grid::world::Random::new()),
+            input: WinitInputHelper::new(),
+            paused: false,
+            tick: 0,
+            post_process_mode: PostProcessMode::Passthrough,
+            blend_mode: BlendMode::Over,
+            accumulator: 0.0,
+            speed_index: DEFAULT_SPEED_INDEX,
+            last_update: Instant::now(),
+            step_requested: false,
+            gui_wants_pointer: false,
+            gui_wants_keyboard: false,
+        }
+    }
+
+    fn reset(&mut self) {
+        let post_process_mode = self.post_process_mode;
+        let blend_mode = self.blend_mode;
+        let speed_index = self.speed_index;
+        *self = Self::new();
+        self.post_process_mode = post_process_mode;
+        self.blend_mode = blend_mode;
+        self.speed_index = speed_index;
+    }
+
+    fn step(&mut self) {
+        self.world.update();
+        self.tick += 1;
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(start))]
+pub fn main() -> Result<(), Error> {
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
     env_logger::init();
+    #[cfg(target_arch = "wasm32")]
+    {
+        console_error_panic_hook::set_once();
+        console_log::init_with_level(log::Level::Warn).expect("error initializing console logger");
+    }
+
     let event_loop = EventLoop::new().unwrap();
-    let window = build_window(&event_loop);
-    let mut pixels = build_pixels(&window)?;
+    run_app(event_loop)
+}
+
+#[cfg(target_os = "android")]
+#[no_mangle]
+fn android_main(app: AndroidApp) {
+    use winit::platform::android::EventLoopBuilderExtAndroid;
+
+    android_logger::init_once(android_logger::Config::default().with_max_level(log::LevelFilter::Warn));
+
+    let event_loop = EventLoop::builder().with_android_app(app).build().unwrap();
+    run_app(event_loop).expect("event loop failed");
+}
+
+/// Builds the event loop's initial `Window`/`Pixels`, if the platform can
+/// provide them up front, then runs it.
+fn run_app(event_loop: EventLoop<()>) -> Result<(), Error> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        // winit's wasm event loop can't block on the async surface setup,
+        // so we defer entering the loop until `pixels` is ready.
+        let window = build_window(&event_loop);
+        attach_canvas(&window);
+        wasm_bindgen_futures::spawn_local(async move {
+            let pixels = build_pixels(&window).await.expect("build_pixels failed");
+            let gui = build_gui(&event_loop, &window, &pixels);
+            let post_process = PostProcessor::new(&pixels, WIDTH, HEIGHT);
+            run(event_loop, Some(window), Some(pixels), Some(gui), Some(post_process))
+                .expect("event loop failed");
+        });
+        return Ok(());
+    }
+
+    // On Android the GPU surface is only valid once the activity has been
+    // resumed, so native and Android platforms alike build their `Window`,
+    // `Pixels`, `Gui` and `PostProcessor` lazily, the first time
+    // `Event::Resumed` fires.
+    #[cfg(not(target_arch = "wasm32"))]
+    run(event_loop, None, None, None, None)
+}
+
+fn build_gui<T>(event_loop: &EventLoopWindowTarget<T>, window: &Window, pixels: &Pixels) -> Gui {
+    let window_size = window.inner_size();
+    Gui::new(
+        event_loop,
+        window_size.width,
+        window_size.height,
+        window.scale_factor() as f32,
+        pixels,
+    )
+}
+
+fn run(
+    event_loop: EventLoop<()>,
+    mut window: Option<Window>,
+    mut pixels: Option<Pixels>,
+    mut gui: Option<Gui>,
+    mut post_process: Option<PostProcessor>,
+) -> Result<(), Error> {
+    let mut state = AppState::new();
+
+    let res = event_loop.run(move |event, elwt| {
+        // Keep polling between input events so the fixed-timestep
+        // accumulator below advances even while the app is otherwise idle.
+        elwt.set_control_flow(ControlFlow::Poll);
+
+        if let Event::Resumed = event {
+            if window.is_none() {
+                let new_window = build_window(elwt);
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    pixels = match pollster::block_on(build_pixels(&new_window)) {
+                        Ok(pixels) => {
+                            gui = Some(build_gui(elwt, &new_window, &pixels));
+                            post_process = Some(PostProcessor::new(&pixels, WIDTH, HEIGHT));
+                            Some(pixels)
+                        }
+                        Err(err) => {
+                            log_error("build_pixels", err);
+                            elwt.exit();
+                            return;
+                        }
+                    };
+                }
+                window = Some(new_window);
+            }
+        }
+        if let Event::Suspended = event {
+            // The window and its GPU surface are invalidated while the
+            // activity is backgrounded; drop them so `Resumed` rebuilds
+            // them from scratch.
+            pixels = None;
+            gui = None;
+            post_process = None;
+            window = None;
+        }
 
-    let mut world = World::new(WIDTH as usize, HEIGHT as usize, evo_grid::world::Random::new());
+        let (Some(window), Some(pixels), Some(gui), Some(post_process)) =
+            (&window, &mut pixels, &mut gui, &mut post_process)
+        else {
+            return;
+        };
 
-    let mut input = WinitInputHelper::new();
-    let mut paused = false;
+        // Let egui see the event before WinitInputHelper does, so a click or
+        // keystroke aimed at the panel doesn't also drive the simulation.
+        // `state.input.update` only reports back on the batch-ending step
+        // event, so OR this event's `consumed` into the matching
+        // `gui_wants_*` flag rather than acting on it directly; those are
+        // read (and cleared) below.
+        if let Event::WindowEvent { event: window_event, .. } = &event {
+            if gui.handle_event(window, window_event).consumed {
+                match window_event {
+                    WindowEvent::KeyboardInput { .. } | WindowEvent::ModifiersChanged(_) => {
+                        state.gui_wants_keyboard = true;
+                    }
+                    _ => state.gui_wants_pointer = true,
+                }
+            }
+        }
 
-    let res = event_loop.run(|event, elwt| {
         // The one and only event that winit_input_helper doesn't have for us...
         if let Event::WindowEvent {
             event: WindowEvent::RedrawRequested,
             ..
         } = event
         {
-            draw_grid_cells(&world, pixels.frame_mut());
-            if let Err(err) = pixels.render() {
-                log_error("pixels.render", err);
+            draw_grid_cells(&state.world, pixels.frame_mut(), state.blend_mode);
+            let density = substance_density(&state.world);
+            let actions = gui.prepare(window, &state.world, state.tick, state.paused);
+            apply_gui_actions(&mut state, actions);
+            // `prepare` just refreshed egui's hover/focus state for this
+            // frame; fold it in too so a click that lands while the pointer
+            // is merely hovering a button (not yet past egui_winit's own
+            // consumption threshold) still gates out. Keyboard focus, unlike
+            // pointer hover, only applies to an actually-focused widget, so
+            // this can't false-gate the shortcuts just from cursor position.
+            state.gui_wants_pointer |= gui.wants_pointer_input();
+            state.gui_wants_keyboard |= gui.wants_keyboard_input();
+
+            let time = state.tick as f32 * TICK_DURATION;
+            let render_result = pixels.render_with(|encoder, render_target, context| {
+                post_process.render(encoder, render_target, context, state.post_process_mode, time, &density);
+                gui.render(encoder, render_target, context);
+                Ok(())
+            });
+            if let Err(err) = render_result {
+                log_error("pixels.render_with", err);
                 elwt.exit();
                 return;
             }
@@ -47,38 +290,126 @@ fn main() -> Result<(), Error> {
 
         // For everything else, for let winit_input_helper collect events to build its state.
         // It returns `true` when it is time to update our game state and request a redraw.
-        if input.update(&event) {
+        if state.input.update(&event) {
             // Close events
-            if input.key_pressed(KeyCode::Escape) || input.close_requested() {
+            if state.input.key_pressed(KeyCode::Escape) || state.input.close_requested() {
                 elwt.exit();
                 return;
             }
-            if input.key_pressed(KeyCode::KeyP) {
-                paused = !paused;
+            if !state.gui_wants_keyboard && state.input.key_pressed(KeyCode::KeyP) {
+                state.paused = !state.paused;
             }
-            if input.key_pressed_os(KeyCode::Space) {
+            if !state.gui_wants_keyboard && state.input.key_pressed_os(KeyCode::Space) {
                 // Space is frame-step, so ensure we're paused
-                paused = true;
+                state.paused = true;
+                state.step_requested = true;
+            }
+            if !state.gui_wants_keyboard && state.input.key_pressed(KeyCode::KeyM) {
+                // Cycle passthrough -> scanlines -> heatmap post-processing
+                state.post_process_mode = state.post_process_mode.next();
+            }
+            if !state.gui_wants_keyboard && state.input.key_pressed(KeyCode::BracketRight) {
+                state.speed_index = (state.speed_index + 1).min(SPEEDS.len() - 1);
+            }
+            if !state.gui_wants_keyboard && state.input.key_pressed(KeyCode::BracketLeft) {
+                state.speed_index = state.speed_index.saturating_sub(1);
+            }
+            if !state.gui_wants_keyboard && state.input.key_pressed(KeyCode::KeyB) {
+                // Cycle the creature/substance compositing blend mode
+                state.blend_mode = state.blend_mode.next();
             }
 
             // Resize the window
-            if let Some(size) = input.window_resized() {
+            if let Some(size) = state.input.window_resized() {
                 if let Err(err) = pixels.resize_surface(size.width, size.height) {
                     log_error("pixels.resize_surface", err);
                     elwt.exit();
                     return;
                 }
+                gui.resize(size.width, size.height);
+            }
+            if let Some(scale_factor) = state.input.scale_factor_changed() {
+                gui.scale_factor(scale_factor);
+            }
+
+            // Click-to-interact: paint creatures (left button) and substance
+            // (right button) onto the grid under the cursor, erasing instead
+            // while Shift is held. Ignored while the cursor is over the egui
+            // panel so clicking a button there doesn't also paint the world
+            // cell underneath it.
+            if !state.gui_wants_pointer {
+                paint_at_cursor(&mut state, pixels);
+            }
+
+            // Fixed-timestep accumulator: run zero or more ticks to catch
+            // the simulation up to wall-clock time, independent of the
+            // display's refresh rate. Pausing freezes the accumulator
+            // rather than draining it, so resuming doesn't burst-update.
+            // `elapsed` is clamped so a stall (startup, a backgrounded
+            // Android activity, a debugger pause) can't dump a huge catch-up
+            // burst into the accumulator in one frame.
+            let now = Instant::now();
+            let elapsed = (now - state.last_update).as_secs_f32().min(MAX_FRAME_TIME);
+            state.last_update = now;
+            if !state.paused {
+                state.accumulator += elapsed * SPEEDS[state.speed_index];
+                while state.accumulator >= TICK_DURATION {
+                    state.step();
+                    state.accumulator -= TICK_DURATION;
+                }
             }
-            if !paused || input.key_pressed_os(KeyCode::Space) {
-                world.update();
+            if state.step_requested {
+                state.step();
+                state.accumulator = 0.0;
+                state.step_requested = false;
             }
+            // This batch's shortcuts and painting have been gated; start
+            // the next batch assuming egui wants nothing until told otherwise.
+            state.gui_wants_pointer = false;
+            state.gui_wants_keyboard = false;
             window.request_redraw();
         }
     });
     res.map_err(|e| Error::UserDefined(Box::new(e)))
 }
 
-fn build_window(event_loop: &EventLoop<()>) -> Window {
+/// Maps the cursor's physical position through the window-to-grid scale and,
+/// if a mouse button is held, paints the cell underneath it: left button for
+/// creatures, right button for substance, Shift held to erase instead.
+fn paint_at_cursor(state: &mut AppState, pixels: &Pixels) {
+    let Some(cursor) = state.input.cursor() else {
+        return;
+    };
+    let erasing = state.input.key_held(KeyCode::ShiftLeft) || state.input.key_held(KeyCode::ShiftRight);
+
+    if state.input.mouse_held(0) {
+        if let Ok((x, y)) = pixels.window_pos_to_pixel(cursor) {
+            let creature = if erasing { None } else { Some(Creature { color: PAINT_CREATURE_COLOR }) };
+            state.world.set_creature(x, y, creature);
+        }
+    }
+    if state.input.mouse_held(1) {
+        if let Ok((x, y)) = pixels.window_pos_to_pixel(cursor) {
+            let amount = if erasing { -PAINT_SUBSTANCE_AMOUNT } else { PAINT_SUBSTANCE_AMOUNT };
+            state.world.add_substance(x, y, amount);
+        }
+    }
+}
+
+fn apply_gui_actions(state: &mut AppState, actions: gui::GuiActions) {
+    if actions.toggle_paused {
+        state.paused = !state.paused;
+    }
+    if actions.step {
+        state.paused = true;
+        state.step_requested = true;
+    }
+    if actions.reset {
+        state.reset();
+    }
+}
+
+fn build_window(event_loop: &EventLoopWindowTarget<()>) -> Window {
     let size = LogicalSize::new(WIDTH as f64, HEIGHT as f64);
     let scaled_size = LogicalSize::new(WIDTH as f64 * 3.0, HEIGHT as f64 * 3.0);
     WindowBuilder::new()
@@ -89,26 +420,51 @@ fn build_window(event_loop: &EventLoop<()>) -> Window {
         .unwrap()
 }
 
-fn build_pixels(window: &Window) -> Result<Pixels, Error> {
+async fn build_pixels(window: &Window) -> Result<Pixels, Error> {
     let window_size = window.inner_size();
     let surface_texture = SurfaceTexture::new(window_size.width, window_size.height, &window);
     PixelsBuilder::new(WIDTH, HEIGHT, surface_texture)
         .clear_color(Color::WHITE)
-        .build()
+        .build_async()
+        .await
+}
+
+#[cfg(target_arch = "wasm32")]
+fn attach_canvas(window: &Window) {
+    use web_sys::Element;
+
+    web_sys::window()
+        .and_then(|win| win.document())
+        .and_then(|doc| doc.get_element_by_id("evo-grid-pixels"))
+        .and_then(|dst| {
+            let canvas = Element::from(window.canvas()?);
+            dst.append_child(&canvas).ok()
+        })
+        .expect("couldn't attach canvas to document body");
 }
 
-fn draw_grid_cells(world: &World, screen: &mut [u8]) {
+fn draw_grid_cells(world: &World, screen: &mut [u8], blend_mode: BlendMode) {
     debug_assert_eq!(screen.len(), 4 * world.num_cells());
     for (cell, pixel) in world.cells_iter().zip(screen.chunks_exact_mut(4)) {
-        let color_rgba = render_cell(cell);
+        let color_rgba = render_cell(cell, blend_mode);
         pixel.copy_from_slice(&color_rgba);
     }
 }
 
-fn render_cell(cell: &GridCell) -> [u8; 4] {
-    let mut color_rgba = render_cell_creature(cell.creature);
-    color_rgba = alpha_blend(render_cell_substance(cell.substance), color_rgba);
-    color_rgba
+/// Each cell's raw substance amount (independent of `blend_mode` and
+/// whatever color the substance renders as), for the post-process heatmap
+/// mode to key off instead of the composited pixel buffer.
+fn substance_density(world: &World) -> Vec<u8> {
+    world
+        .cells_iter()
+        .map(|cell| (cell.substance.map_or(0.0, |substance| substance.amount).clamp(0.0, 1.0) * 0xff as f32) as u8)
+        .collect()
+}
+
+fn render_cell(cell: &GridCell, blend_mode: BlendMode) -> [u8; 4] {
+    let creature = render_cell_creature(cell.creature);
+    let substance = render_cell_substance(cell.substance);
+    composite(substance, creature, blend_mode)
 }
 
 fn render_cell_creature(cell_creature: Option<Creature>) -> [u8; 4] {
@@ -130,8 +486,37 @@ fn render_cell_substance(cell_substance: Option<Substance>) -> [u8; 4] {
     }
 }
 
-// From https://en.wikipedia.org/wiki/Alpha_compositing
-fn alpha_blend(above: [u8; 4], below: [u8; 4]) -> [u8; 4] {
+/// How the substance layer (`above`) is composited onto the creature layer
+/// (`below`). `Over` is plain alpha compositing; the rest recolor `above`'s
+/// contribution before compositing it, so overlapping substance plumes can
+/// glow (`Additive`/`Screen`) or darken (`Multiply`) instead of just
+/// washing out toward the substance color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlendMode {
+    Over,
+    Additive,
+    Multiply,
+    Screen,
+}
+
+impl BlendMode {
+    fn next(self) -> Self {
+        match self {
+            Self::Over => Self::Additive,
+            Self::Additive => Self::Multiply,
+            Self::Multiply => Self::Screen,
+            Self::Screen => Self::Over,
+        }
+    }
+}
+
+// The alpha formula is from https://en.wikipedia.org/wiki/Alpha_compositing;
+// the `Multiply` and `Screen` color formulas are the usual Porter-Duff-over
+// composite of each mode's blended color with the straight (un-blended)
+// layers, weighted by how much of each layer is actually present (its alpha).
+// `Additive` instead sums both alpha-weighted layers, clamped to the result
+// alpha.
+fn composite(above: [u8; 4], below: [u8; 4], mode: BlendMode) -> [u8; 4] {
     let above = color_as_fractions(above);
     let below = color_as_fractions(below);
 
@@ -141,7 +526,24 @@ fn alpha_blend(above: [u8; 4], below: [u8; 4]) -> [u8; 4] {
 
     let mut result: [f32; 4] = [0.0, 0.0, 0.0, result_alpha];
     for i in 0..=2 {
-        result[i] = (above[i] * above_alpha + below[i] * below_alpha * (1.0 - above_alpha)) / result_alpha;
+        let blended = match mode {
+            BlendMode::Over => above[i] * above_alpha + below[i] * below_alpha * (1.0 - above_alpha),
+            BlendMode::Additive => {
+                (above[i] * above_alpha + below[i] * below_alpha).min(result_alpha)
+            }
+            BlendMode::Multiply => {
+                above[i] * below[i] * above_alpha * below_alpha
+                    + above[i] * above_alpha * (1.0 - below_alpha)
+                    + below[i] * below_alpha * (1.0 - above_alpha)
+            }
+            BlendMode::Screen => {
+                let screened = 1.0 - (1.0 - above[i]) * (1.0 - below[i]);
+                screened * above_alpha * below_alpha
+                    + above[i] * above_alpha * (1.0 - below_alpha)
+                    + below[i] * below_alpha * (1.0 - above_alpha)
+            }
+        };
+        result[i] = blended / result_alpha;
     }
     color_as_bytes(result)
 }
@@ -168,3 +570,66 @@ fn log_error<E: std::error::Error + 'static>(method_name: &str, err: E) {
         error!("  Caused by: {source}");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPAQUE_RED: [u8; 4] = [0xff, 0x00, 0x00, 0xff];
+    const OPAQUE_BLUE: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+    const TRANSPARENT: [u8; 4] = [0x00, 0x00, 0x00, 0x00];
+    const ALL_MODES: [BlendMode; 4] =
+        [BlendMode::Over, BlendMode::Additive, BlendMode::Multiply, BlendMode::Screen];
+
+    #[test]
+    fn over_matches_the_original_alpha_blend_formula() {
+        // `BlendMode::Over` replaced the old hard-coded `alpha_blend`
+        // function; it must still compute the same Wikipedia alpha
+        // compositing formula that function did.
+        let above = [0x80, 0x40, 0x20, 0x80];
+        let below = [0x10, 0x20, 0x30, 0xc0];
+
+        let above_f = color_as_fractions(above);
+        let below_f = color_as_fractions(below);
+        let above_alpha = above_f[3];
+        let below_alpha = below_f[3];
+        let result_alpha = above_alpha + below_alpha * (1.0 - above_alpha);
+        let mut expected = [0.0; 4];
+        expected[3] = result_alpha;
+        for i in 0..=2 {
+            expected[i] =
+                (above_f[i] * above_alpha + below_f[i] * below_alpha * (1.0 - above_alpha)) / result_alpha;
+        }
+
+        assert_eq!(composite(above, below, BlendMode::Over), color_as_bytes(expected));
+    }
+
+    #[test]
+    fn fully_transparent_above_leaves_below_unchanged_in_every_mode() {
+        for mode in ALL_MODES {
+            assert_eq!(composite(TRANSPARENT, OPAQUE_BLUE, mode), OPAQUE_BLUE);
+        }
+    }
+
+    #[test]
+    fn over_with_both_opaque_keeps_only_above() {
+        assert_eq!(composite(OPAQUE_RED, OPAQUE_BLUE, BlendMode::Over), OPAQUE_RED);
+    }
+
+    #[test]
+    fn additive_with_both_opaque_sums_channels() {
+        assert_eq!(composite(OPAQUE_RED, OPAQUE_BLUE, BlendMode::Additive), [0xff, 0x00, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn multiply_with_both_opaque_multiplies_channels() {
+        // Red (0xff,0,0) * Blue (0,0,0xff) is black in every channel.
+        assert_eq!(composite(OPAQUE_RED, OPAQUE_BLUE, BlendMode::Multiply), [0x00, 0x00, 0x00, 0xff]);
+    }
+
+    #[test]
+    fn screen_with_both_opaque_screens_channels() {
+        // screen(1,0) = 1, screen(0,0) = 0, so red and blue fully combine to magenta.
+        assert_eq!(composite(OPAQUE_RED, OPAQUE_BLUE, BlendMode::Screen), [0xff, 0x00, 0xff, 0xff]);
+    }
+}
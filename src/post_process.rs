@@ -0,0 +1,264 @@
+use pixels::wgpu::util::DeviceExt;
+use pixels::{wgpu, Pixels, PixelsContext};
+
+/// Post-processing modes cycled with a hotkey, applied to the scaled-up
+/// pixel buffer in place of `Pixels`' own passthrough scaling renderer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PostProcessMode {
+    Passthrough,
+    Scanlines,
+    Heatmap,
+}
+
+impl PostProcessMode {
+    pub(crate) fn next(self) -> Self {
+        match self {
+            Self::Passthrough => Self::Scanlines,
+            Self::Scanlines => Self::Heatmap,
+            Self::Heatmap => Self::Passthrough,
+        }
+    }
+
+    fn as_u32(self) -> u32 {
+        match self {
+            Self::Passthrough => 0,
+            Self::Scanlines => 1,
+            Self::Heatmap => 2,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    mode: u32,
+    time: f32,
+    height: u32,
+    _padding: u32,
+}
+
+/// Renders the low-res pixel buffer through a selectable WGSL fragment
+/// shader (see `post_process.wgsl`), replacing `context.scaling_renderer`.
+pub(crate) struct PostProcessor {
+    sampler: wgpu::Sampler,
+    bind_group_layout: wgpu::BindGroupLayout,
+    uniform_buffer: wgpu::Buffer,
+    render_pipeline: wgpu::RenderPipeline,
+    /// Single-channel (`R8Unorm`) texture of each cell's raw substance
+    /// amount, independent of however the creature/substance layers were
+    /// composited into the main render target. Heatmap mode samples this
+    /// instead of the composited color so a bright creature doesn't read
+    /// as "high density".
+    density_texture: wgpu::Texture,
+    width: u32,
+    height: u32,
+}
+
+impl PostProcessor {
+    pub(crate) fn new(pixels: &Pixels, width: u32, height: u32) -> Self {
+        let device = pixels.device();
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("post-process shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("post_process.wgsl").into()),
+        });
+
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("post-process uniforms"),
+            contents: bytemuck::bytes_of(&Uniforms {
+                mode: PostProcessMode::Passthrough.as_u32(),
+                time: 0.0,
+                height,
+                _padding: 0,
+            }),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("post-process sampler"),
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        let density_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("substance density texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("post-process bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("post-process pipeline layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("post-process pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: pixels.render_texture_format(),
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+
+        Self {
+            sampler,
+            bind_group_layout,
+            uniform_buffer,
+            render_pipeline,
+            density_texture,
+            width,
+            height,
+        }
+    }
+
+    pub(crate) fn render(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        render_target: &wgpu::TextureView,
+        context: &PixelsContext,
+        mode: PostProcessMode,
+        time: f32,
+        density: &[u8],
+    ) {
+        context.queue.write_buffer(
+            &self.uniform_buffer,
+            0,
+            bytemuck::bytes_of(&Uniforms {
+                mode: mode.as_u32(),
+                time,
+                height: self.height,
+                _padding: 0,
+            }),
+        );
+
+        debug_assert_eq!(density.len(), (self.width * self.height) as usize);
+        context.queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &self.density_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            density,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(self.width),
+                rows_per_image: Some(self.height),
+            },
+            wgpu::Extent3d {
+                width: self.width,
+                height: self.height,
+                depth_or_array_layers: 1,
+            },
+        );
+        let density_view = self.density_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let bind_group = context.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("post-process bind group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&context.texture_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(&density_view),
+                },
+            ],
+        });
+
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("post-process pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: render_target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        rpass.set_pipeline(&self.render_pipeline);
+        rpass.set_bind_group(0, &bind_group, &[]);
+        rpass.draw(0..3, 0..1);
+    }
+}